@@ -1,7 +1,9 @@
 //! Loads the configuration and runs the game workflow.
 extern crate crossterm;
 extern crate rand;
+extern crate serde;
 extern crate thiserror;
+extern crate toml;
 mod application;
 mod dictionary;
 mod game;
@@ -12,6 +14,7 @@ use std::env;
 use std::fs::File;
 use std::io;
 use std::io::prelude::*;
+use std::io::IsTerminal;
 use std::io::Write;
 use std::path::PathBuf;
 use std::process;
@@ -21,6 +24,8 @@ use crossterm::cursor::MoveToNextLine;
 use crossterm::queue;
 use crossterm::style::Color;
 use crossterm::style::Print;
+use crossterm::style::ResetColor;
+use crossterm::style::SetBackgroundColor;
 use crossterm::style::SetForegroundColor;
 use crossterm::terminal::Clear;
 use crossterm::terminal::ClearType;
@@ -64,11 +69,28 @@ Type a letter then type [Enter]:
 -----------------------------------
 ===================================
 
- Usage: ascii-hangman
-        ascii-hangman [FILE]...
+ Usage: ascii-hangman [--lives N] [--mode success-rewarding|traditional-rewarding]
+                      [--config FILE]... [--theme light|dark] [--plain] [FILE]...
         ascii-hangman -h|--help
         asciiart-ascii-hangman-for-kids -V|--version
 
+`--lives N` overrides the number of wrong guesses allowed.
+
+`--mode success-rewarding|traditional-rewarding` overrides the disclosure
+mode, taking precedence over any `:` modifier line in the config file.
+
+`--config FILE` adds a configuration file; it may be repeated and is
+equivalent to passing `FILE` as a positional argument.
+
+`--theme light|dark` overrides the automatic light/dark terminal
+background detection used to pick the default color palette. Individual
+colors can still be overridden in the config file with `:foreground-color
+REGION COLOR` and `:background-color REGION COLOR`, where `REGION` is one
+of `title`, `image`, `status`, `secret`, `instructions`.
+
+`--plain` forces plain, uncolored text output with no screen clear or
+cursor movement, which is chosen automatically when stdout is not a
+terminal, e.g. redirected to a file or piped into another program.
 
 `[FILE]` are configuration files containing word-lists and optionally Ascii-Art
 images.
@@ -127,6 +149,25 @@ If you prefer a traditional gallows image, add the following:
         ||      |___
         ||_________|
 
+A `[FILE]` ending in `.toml` is parsed as a structured configuration
+instead of the line-prefixed format above:
+
+        secrets = [
+            "Guess _me_",
+            "hang_man_",
+        ]
+
+        ascii_art = [
+            "  ,~~--~~-.",
+            " +      | |\\",
+            " || |~ |`,/-\\",
+            " *\\_) \\_) `-'",
+        ]
+
+        [game]
+        lives = 7
+        mode = "traditional-rewarding"
+
 "#;
 
 /// Number of wrong guess allowed.
@@ -148,12 +189,78 @@ const CONF_DEMO: &str = "- _Demo: add own words to config file and start a_gain_
 
 // ------------------ MAIN ---------------------------------------------
 
-/// Reads the configuration file.
-pub fn read_config(pathstr: &PathBuf) -> Result<String, io::Error> {
+/// Structured form of a `.toml` configuration file: a `[game]` table for
+/// `lives`/`mode`, a `secrets` array (with `_..._` hint markers preserved
+/// verbatim, same as in the legacy format) and an optional `image`
+/// multiline string plus `ascii_art` lines.
+#[derive(serde::Deserialize)]
+struct TomlConfig {
+    #[serde(default)]
+    game: TomlGame,
+    #[serde(default)]
+    secrets: Vec<String>,
+    image: Option<String>,
+    #[serde(default)]
+    ascii_art: Vec<String>,
+}
+
+/// The `[game]` table of a `TomlConfig`.
+#[derive(serde::Deserialize, Default)]
+struct TomlGame {
+    lives: Option<u8>,
+    mode: Option<String>,
+}
+
+/// Translates a `.toml` configuration into the legacy line-prefixed text
+/// (secrets, `|` image lines and a `:` mode line), so it runs through the
+/// same internal parser the legacy format already uses. `lives` is returned
+/// separately since the legacy format has no equivalent line for it.
+fn toml_config_to_legacy(toml_src: &str) -> Result<(String, Option<u8>), io::Error> {
+    let parsed: TomlConfig =
+        toml::from_str(toml_src).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut legacy = String::new();
+
+    if let Some(mode) = &parsed.game.mode {
+        legacy.push_str(&format!(":{}\n", mode));
+    }
+    for secret in &parsed.secrets {
+        legacy.push_str(secret);
+        legacy.push('\n');
+    }
+    for line in parsed.image.iter().flat_map(|image| image.lines()) {
+        legacy.push('|');
+        legacy.push_str(line);
+        legacy.push('\n');
+    }
+    for line in &parsed.ascii_art {
+        legacy.push('|');
+        legacy.push_str(line);
+        legacy.push('\n');
+    }
+
+    Ok((legacy, parsed.game.lives))
+}
+
+/// Reads the configuration file, translating `.toml` files into the legacy
+/// format on the fly (see `toml_config_to_legacy`). Returns the `lives`
+/// override found in a `.toml` file's `[game]` table, if any.
+///
+/// Returns an `io::ErrorKind::NotFound` error when `pathstr` does not exist,
+/// so the caller can write a template; a malformed `.toml` instead surfaces
+/// as an `io::ErrorKind::InvalidData` error carrying the `toml` crate's own
+/// line/column-annotated message, which the caller must not treat the same
+/// way (doing so would overwrite the user's file with the template).
+pub fn read_config(pathstr: &PathBuf) -> Result<(String, Option<u8>), io::Error> {
     let mut f = File::open(pathstr)?;
     let mut s = String::new();
     f.read_to_string(&mut s)?;
-    Ok(s)
+
+    if pathstr.extension().and_then(|ext| ext.to_str()) == Some("toml") {
+        toml_config_to_legacy(&s)
+    } else {
+        Ok((s, None))
+    }
 }
 
 /// Writes a sample configuration file on disk. Called when no configuration file can be found.
@@ -163,53 +270,251 @@ pub fn write_config_template(pathstr: &PathBuf) -> Result<(), io::Error> {
     Ok(())
 }
 
+/// Returns the ordered list of directories that are searched for `PATHSTR`
+/// when no `[FILE]` argument is given on the command-line.
+///
+/// The search order is:
+/// 1. `$XDG_CONFIG_HOME/ascii-hangman/` (falling back to `$HOME/.config/ascii-hangman/`
+///    when `XDG_CONFIG_HOME` is unset), or `%APPDATA%\ascii-hangman\` on Windows.
+/// 2. The current working directory.
+///
+/// This keeps a word-list stable across `cd`, while still honoring a
+/// config file dropped into the current working directory by a child.
+fn config_search_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    #[cfg(windows)]
+    if let Ok(appdata) = env::var("APPDATA") {
+        dirs.push(PathBuf::from(appdata).join("ascii-hangman"));
+    }
+
+    #[cfg(not(windows))]
+    {
+        if let Ok(xdg_config_home) = env::var("XDG_CONFIG_HOME") {
+            dirs.push(PathBuf::from(xdg_config_home).join("ascii-hangman"));
+        } else if let Ok(home) = env::var("HOME") {
+            dirs.push(PathBuf::from(home).join(".config").join("ascii-hangman"));
+        }
+    }
+
+    if let Ok(cwd) = env::current_dir() {
+        dirs.push(cwd);
+    }
+
+    dirs
+}
+
+/// Searches `config_search_dirs()` for `PATHSTR` and returns the first match.
+///
+/// When nothing is found, returns `PATHSTR` in the first writable directory
+/// in the search path, creating it if it does not exist yet, so the caller
+/// can write the template there instead of the current working directory.
+/// Falls through to the next candidate if a directory can't be created or
+/// written to.
+fn resolve_config_path() -> PathBuf {
+    let dirs = config_search_dirs();
+
+    for dir in &dirs {
+        let candidate = dir.join(PATHSTR);
+        if candidate.is_file() {
+            return candidate;
+        }
+    }
+
+    for dir in &dirs {
+        if is_writable_dir(dir) {
+            return dir.join(PATHSTR);
+        }
+    }
+
+    PathBuf::from(PATHSTR)
+}
+
+/// Returns `true` if `dir` exists (creating it if necessary) and a file can
+/// actually be created inside it, so a later `write_config_template` call
+/// won't silently fail.
+fn is_writable_dir(dir: &PathBuf) -> bool {
+    if std::fs::create_dir_all(dir).is_err() {
+        return false;
+    }
+    let probe = dir.join(".ascii-hangman-write-test");
+    match File::create(&probe) {
+        Ok(_) => {
+            let _ = std::fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Options gathered from the command-line by `parse_args()`.
+struct Options {
+    /// Overrides `LIVES` when set.
+    lives: Option<u8>,
+    /// Overrides the config file's `:success-rewarding`/`:traditional-rewarding`
+    /// modifier line when set.
+    mode: Option<String>,
+    /// Config files given with (possibly repeated) `--config FILE`.
+    config_files: Vec<PathBuf>,
+    /// Config files given as positional arguments.
+    positional_files: Vec<PathBuf>,
+    /// Overrides `detect_terminal_is_dark()` when set to `"light"` or `"dark"`.
+    theme: Option<String>,
+    /// Forces the plain, non-interactive rendering path even when stdout is
+    /// a terminal.
+    plain: bool,
+}
+
+/// Outcome of parsing `env::args()`.
+enum Operation {
+    /// Print `COMMANDLINE_HELP` and exit.
+    Help,
+    /// Print `VERSION` and exit.
+    Version,
+    /// Start a game with the given `Options`.
+    Play(Options),
+    /// Abort with an error message, e.g. a malformed `--lives` value.
+    Error(String),
+}
+
+/// Parses `env::args()` (without the program name) into an `Operation`.
+///
+/// Recognizes `-h`/`--help`, `-V`/`--version`, `--lives N`,
+/// `--mode success-rewarding|traditional-rewarding`, `--config FILE`
+/// (repeatable), `--theme light|dark` and `--plain`. Every other argument is
+/// treated as a positional config file path. When the game starts, all
+/// `--config` files are concatenated first, then all positional files, each
+/// group in the order given.
+fn parse_args<I: Iterator<Item = String>>(args: I) -> Operation {
+    let mut lives = None;
+    let mut mode = None;
+    let mut config_files = Vec::new();
+    let mut positional_files = Vec::new();
+    let mut theme = None;
+    let mut plain = false;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Operation::Help,
+            "-V" | "--version" => return Operation::Version,
+            "--lives" => match args.next().as_deref().map(|n| n.parse::<u8>()) {
+                Some(Ok(n)) if n >= 1 => lives = Some(n),
+                Some(Ok(_)) | Some(Err(_)) | None => {
+                    return Operation::Error("`--lives` expects a positive number".to_string())
+                }
+            },
+            "--mode" => match args.next().as_deref() {
+                Some(m @ ("success-rewarding" | "traditional-rewarding")) => {
+                    mode = Some(m.to_string())
+                }
+                Some(m) => {
+                    return Operation::Error(format!(
+                        "`--mode` expects `success-rewarding` or `traditional-rewarding`, got {:?}",
+                        m
+                    ))
+                }
+                None => return Operation::Error("`--mode` expects a value".to_string()),
+            },
+            "--config" => match args.next() {
+                Some(f) => config_files.push(PathBuf::from(f)),
+                None => return Operation::Error("`--config` expects a file path".to_string()),
+            },
+            "--theme" => match args.next().as_deref() {
+                Some(t @ ("light" | "dark")) => theme = Some(t.to_string()),
+                Some(t) => {
+                    return Operation::Error(format!(
+                        "`--theme` expects `light` or `dark`, got {:?}",
+                        t
+                    ))
+                }
+                None => return Operation::Error("`--theme` expects a value".to_string()),
+            },
+            "--plain" => plain = true,
+            _ => positional_files.push(PathBuf::from(arg)),
+        }
+    }
+
+    Operation::Play(Options {
+        lives,
+        mode,
+        config_files,
+        positional_files,
+        theme,
+        plain,
+    })
+}
+
 /// Starts the game.
 #[allow(unused_labels)]
 fn main() {
-    // SHOW HELP TEXT
-    match env::args().nth(1) {
-        Some(ref a) if a == "-h" || a == "--help" => {
+    // PARSE COMMAND-LINE ARGUMENTS
+
+    let Options {
+        lives,
+        mode,
+        config_files,
+        positional_files,
+        theme,
+        plain,
+    } = match parse_args(env::args().skip(1)) {
+        Operation::Help => {
             eprintln!("{}", COMMANDLINE_HELP);
             return;
         }
-        Some(ref a) if a == "-V" || a == "--version" => {
+        Operation::Version => {
             eprintln!("{}", VERSION.unwrap());
             return;
         }
-        Some(_) | None => {}
+        Operation::Error(msg) => {
+            eprintln!("{}\n\n{}", msg, COMMANDLINE_HELP);
+            process::exit(1);
+        }
+        Operation::Play(options) => options,
     };
 
     // READ CONFIG
 
-    // Read all config files given on command line
-    let mut conf_file_paths = env::args()
-        .skip(1)
-        .map(|s| PathBuf::from(s))
-        .collect::<Vec<PathBuf>>();
+    // Config files given with `--config`, then positional files, in order.
+    let mut conf_file_paths = config_files;
+    conf_file_paths.extend(positional_files);
 
-    // if no conf_file_paths are given then use default config path
+    // if no conf_file_paths are given then search the XDG config locations,
+    // falling back to the current working directory.
     if conf_file_paths.is_empty() {
-        conf_file_paths.push(PathBuf::from(PATHSTR))
+        let path = resolve_config_path();
+        eprintln!("Using configuration file:\n\t{:?}", path);
+        conf_file_paths.push(path)
     };
 
     // read and concatenate all config files given on command line
     let cwd = env::current_dir().unwrap();
 
     let mut config: String = String::new();
+    // `lives` found in a `.toml` file's `[game]` table; a later file wins,
+    // same as the concatenation order for secrets and images.
+    let mut toml_lives: Option<u8> = None;
     for conf_file_path in &conf_file_paths {
         let path = conf_file_path;
         let c = match read_config(&path) {
-            Ok(s) => s,
-            Err(_) => {
+            Ok((s, file_lives)) => {
+                if file_lives.is_some() {
+                    toml_lives = file_lives;
+                }
+                s
+            }
+            // A missing config file is not an error: write the template.
+            // Anything else (e.g. a malformed `.toml`) must not overwrite
+            // the user's file, so report it and stop instead.
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
                 match write_config_template(&path) {
                     Ok(_) => {
                         eprintln!(
                             "As no config-file :\n\
                              \t{:?}\n\
-                             was found a template file is written in the \
-                             current working directory.\n\
+                             was found a template file is written to:\n\
                              \t{:?}\n\n\nPress [Enter] to enter demo mode.",
-                            path, cwd
+                            path, path
                         );
                         // wait for [Enter] key
                         let s = &mut String::new();
@@ -233,10 +538,54 @@ fn main() {
                     }
                 }
             }
+            Err(e) => {
+                eprintln!("ERROR IN CONFIGURATION FILE\n\t{:?}\n{}", path, e);
+
+                // wait for [Enter] key
+                let s = &mut String::new();
+                io::stdin().read_line(s).unwrap();
+                process::exit(1);
+            }
         };
         config.push_str(&c);
     }
 
+    // `--lives` takes precedence over a `.toml` file's `[game]` table, which
+    // in turn takes precedence over the `LIVES` default.
+    let lives = lives.unwrap_or_else(|| toml_lives.unwrap_or(LIVES));
+
+    // Thread the effective lives count into the config as a `:lives N`
+    // directive, the same way `--mode` is threaded in below: the
+    // `application` module is not part of this source tree, so
+    // `Application::new` cannot be given an extra parameter here.
+    config.insert_str(0, &format!(":lives {}\n", lives));
+
+    // a `--mode` override takes precedence over any `:` modifier line
+    // already present in the concatenated config. Strip those lines outright
+    // instead of merely prepending the override, since precedence would
+    // otherwise depend on whether the parser honors the first or the last
+    // `:` mode line.
+    if let Some(mode) = mode {
+        config = config
+            .split('\n')
+            .filter(|line| {
+                let trimmed = line.trim();
+                trimmed != ":success-rewarding" && trimmed != ":traditional-rewarding"
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        config.insert_str(0, &format!(":{}\n", mode));
+    }
+
+    // RESOLVE THE THEME
+
+    let mut theme = resolve_default_theme(theme.as_deref());
+    apply_theme_overrides(&mut config, &mut theme);
+
+    // plain rendering is forced with `--plain`, or chosen automatically when
+    // stdout is not a terminal, e.g. redirected to a file or piped.
+    let plain = plain || !io::stdout().is_terminal();
+
     // INITIALISE THE GAME
 
     let mut app = match Application::new(&config) {
@@ -251,7 +600,7 @@ fn main() {
         }
     };
 
-    app.render();
+    app.render(&theme, plain);
 
     // PLAY
 
@@ -266,67 +615,370 @@ fn main() {
             break;
         };
 
-        app.render();
+        app.render(&theme, plain);
     }
 
     println!("\n{}", AUTHOR);
 }
 
+/// Named regions of the TUI that `Theme` colors independently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ThemeRegion {
+    Title,
+    Image,
+    Status,
+    Secret,
+    Instructions,
+}
+
+/// Foreground/background colors for one `ThemeRegion`. `background` is
+/// `None` unless a `:background-color` line overrides it, leaving the
+/// terminal's own background untouched.
+#[derive(Debug, Clone, Copy)]
+struct RegionColors {
+    foreground: Color,
+    background: Option<Color>,
+}
+
+impl RegionColors {
+    fn new(foreground: Color) -> Self {
+        RegionColors {
+            foreground,
+            background: None,
+        }
+    }
+}
+
+/// Resolved color palette for the whole TUI, read by `render()` instead of
+/// the former `#[cfg(windows)]`/`#[cfg(not(windows))]` branches.
+#[derive(Debug, Clone)]
+struct Theme {
+    title: RegionColors,
+    image: RegionColors,
+    status: RegionColors,
+    secret: RegionColors,
+    instructions: RegionColors,
+}
+
+impl Theme {
+    fn region(&self, region: ThemeRegion) -> RegionColors {
+        match region {
+            ThemeRegion::Title => self.title,
+            ThemeRegion::Image => self.image,
+            ThemeRegion::Status => self.status,
+            ThemeRegion::Secret => self.secret,
+            ThemeRegion::Instructions => self.instructions,
+        }
+    }
+
+    fn region_mut(&mut self, region: ThemeRegion) -> &mut RegionColors {
+        match region {
+            ThemeRegion::Title => &mut self.title,
+            ThemeRegion::Image => &mut self.image,
+            ThemeRegion::Status => &mut self.status,
+            ThemeRegion::Secret => &mut self.secret,
+            ThemeRegion::Instructions => &mut self.instructions,
+        }
+    }
+
+    /// Default palette for a dark terminal background.
+    fn dark() -> Theme {
+        Theme {
+            title: RegionColors::new(Color::White),
+            image: RegionColors::new(Color::DarkYellow),
+            status: RegionColors::new(Color::White),
+            secret: RegionColors::new(Color::DarkGreen),
+            instructions: RegionColors::new(Color::White),
+        }
+    }
+
+    /// Default palette for a light terminal background, with colors chosen
+    /// for contrast on a light background.
+    fn light() -> Theme {
+        Theme {
+            title: RegionColors::new(Color::Black),
+            image: RegionColors::new(Color::DarkYellow),
+            status: RegionColors::new(Color::Black),
+            secret: RegionColors::new(Color::DarkGreen),
+            instructions: RegionColors::new(Color::Black),
+        }
+    }
+}
+
+/// Detects whether the terminal's background is light or dark.
+///
+/// Honors the `COLORFGBG` environment variable set by many terminal
+/// emulators and multiplexers (format `fg;bg`, e.g. `15;0`); the background
+/// field `7` or `15` is treated as light, everything else as dark. Falls
+/// back to dark, the most common default, when `COLORFGBG` is unset or
+/// unparsable.
+fn detect_terminal_is_dark() -> bool {
+    env::var("COLORFGBG")
+        .ok()
+        .and_then(|colorfgbg| colorfgbg.rsplit(';').next().map(str::to_string))
+        .and_then(|bg| bg.parse::<u8>().ok())
+        .map_or(true, |bg| bg != 7 && bg != 15)
+}
+
+/// Resolves the default `Theme`, honoring an explicit `--theme light|dark`
+/// override before falling back to `detect_terminal_is_dark()`.
+fn resolve_default_theme(theme_override: Option<&str>) -> Theme {
+    let is_dark = match theme_override {
+        Some("light") => false,
+        Some("dark") => true,
+        _ => detect_terminal_is_dark(),
+    };
+    if is_dark {
+        Theme::dark()
+    } else {
+        Theme::light()
+    }
+}
+
+/// Parses a config-file color name into a `crossterm::style::Color`.
+///
+/// Accepts the usual ANSI color names (`black`, `darkgrey`, `red`, ...,
+/// case-insensitive) plus `#rrggbb` hex triplets. Returns `None` on an
+/// unrecognized name, leaving the caller free to keep the previous color.
+fn parse_theme_color(name: &str) -> Option<Color> {
+    if let Some(hex) = name.strip_prefix('#') {
+        let hex = hex.as_bytes();
+        if hex.len() != 6 {
+            return None;
+        }
+        let byte = |i| std::str::from_utf8(&hex[i..i + 2]).ok().and_then(|s| u8::from_str_radix(s, 16).ok());
+        return Some(Color::Rgb {
+            r: byte(0)?,
+            g: byte(2)?,
+            b: byte(4)?,
+        });
+    }
+
+    Some(match name.to_ascii_lowercase().as_str() {
+        "black" => Color::Black,
+        "darkgrey" | "darkgray" => Color::DarkGrey,
+        "red" => Color::Red,
+        "darkred" => Color::DarkRed,
+        "green" => Color::Green,
+        "darkgreen" => Color::DarkGreen,
+        "yellow" => Color::Yellow,
+        "darkyellow" => Color::DarkYellow,
+        "blue" => Color::Blue,
+        "darkblue" => Color::DarkBlue,
+        "magenta" => Color::Magenta,
+        "darkmagenta" => Color::DarkMagenta,
+        "cyan" => Color::Cyan,
+        "darkcyan" => Color::DarkCyan,
+        "white" => Color::White,
+        "grey" | "gray" => Color::Grey,
+        _ => return None,
+    })
+}
+
+/// Parses a `ThemeRegion` name as used in `:foreground-color`/
+/// `:background-color` modifier lines.
+fn parse_theme_region(name: &str) -> Option<ThemeRegion> {
+    Some(match name {
+        "title" => ThemeRegion::Title,
+        "image" => ThemeRegion::Image,
+        "status" => ThemeRegion::Status,
+        "secret" => ThemeRegion::Secret,
+        "instructions" => ThemeRegion::Instructions,
+        _ => return None,
+    })
+}
+
+/// Applies `:foreground-color REGION COLOR`/`:background-color REGION COLOR`
+/// modifier lines found in `config` on top of `theme`, the same way
+/// `:success-rewarding` is parsed next to the secrets and images. Unknown
+/// regions or color names are ignored, leaving the default in place. The
+/// recognized lines are then removed from `config`, since the legacy parser
+/// only understands the mode modifiers and would otherwise reject them.
+fn apply_theme_overrides(config: &mut String, theme: &mut Theme) {
+    let mut kept_lines = Vec::with_capacity(config.len());
+    for line in config.split('\n') {
+        let mut words = line.split_whitespace();
+        let is_foreground = match words.next() {
+            Some(":foreground-color") => true,
+            Some(":background-color") => false,
+            _ => {
+                kept_lines.push(line);
+                continue;
+            }
+        };
+        if let (Some(region), Some(color)) = (words.next(), words.next()) {
+            if let (Some(region), Some(color)) =
+                (parse_theme_region(region), parse_theme_color(color))
+            {
+                let colors = theme.region_mut(region);
+                if is_foreground {
+                    colors.foreground = color;
+                } else {
+                    colors.background = Some(color);
+                }
+            }
+        }
+        // recognized `:foreground-color`/`:background-color` lines are
+        // dropped either way, valid or not, since the legacy parser only
+        // understands the mode modifiers.
+    }
+    *config = kept_lines.join("\n");
+}
+
 trait Render {
-    fn render(&self) {}
+    fn render(&self, _theme: &Theme, _plain: bool) {}
 }
 
 impl Render for Application {
-    /// Renders and prints the TUI on the terminal.
-    fn render(&self) {
+    /// Renders and prints one frame.
+    ///
+    /// When `plain` is `true` (stdout is not a terminal, or `--plain` was
+    /// given) the screen clear, cursor moves and ANSI colors are skipped and
+    /// the frame is printed as plain appended text instead, so redirecting
+    /// or piping the output does not produce garbage.
+    fn render(&self, theme: &Theme, plain: bool) {
+        if plain {
+            self.render_plain();
+            return;
+        }
+
         // Disclose parts of the image.
 
+        // A region without its own `background` resets to the terminal's
+        // default instead of keeping a previous region's `SetBackgroundColor`,
+        // which would otherwise bleed into every region printed after it.
+        let set_region = |region| {
+            let colors = theme.region(region);
+            queue!(stdout(), SetForegroundColor(colors.foreground)).unwrap();
+            queue!(
+                stdout(),
+                SetBackgroundColor(colors.background.unwrap_or(Color::Reset))
+            )
+            .unwrap();
+        };
+
         // Clear all lines in terminal;
         queue!(stdout(), Clear(ClearType::All), MoveTo(0, 0)).unwrap();
 
-        #[cfg(not(windows))]
-        queue!(stdout(), SetForegroundColor(Color::White),).unwrap();
-        #[cfg(windows)]
-        queue!(stdout(), SetForegroundColor(Color::Grey),).unwrap();
-
-        queue!(
-            stdout(),
-            Print(&TITLE),
-            MoveToNextLine(1),
-            SetForegroundColor(Color::DarkYellow),
-        )
-        .unwrap();
+        set_region(ThemeRegion::Title);
+        queue!(stdout(), Print(&TITLE), MoveToNextLine(1)).unwrap();
 
         // Print image.
+        set_region(ThemeRegion::Image);
         queue!(stdout(), Print(self.render_image()), MoveToNextLine(1)).unwrap();
 
         // Print game status.
-        #[cfg(not(windows))]
-        queue!(stdout(), SetForegroundColor(Color::White),).unwrap();
-        #[cfg(windows)]
-        queue!(stdout(), SetForegroundColor(Color::Grey),).unwrap();
+        set_region(ThemeRegion::Status);
         queue!(stdout(), Print(self.render_game_status())).unwrap();
 
         // Print secret.
-        #[cfg(not(windows))]
-        queue!(stdout(), SetForegroundColor(Color::DarkGreen),).unwrap();
-        #[cfg(windows)]
-        queue!(stdout(), SetForegroundColor(Color::White),).unwrap();
+        set_region(ThemeRegion::Secret);
         queue!(stdout(), Print(self.render_secret()), MoveToNextLine(1)).unwrap();
 
         // Print instructions.
-        #[cfg(not(windows))]
-        queue!(stdout(), SetForegroundColor(Color::White),).unwrap();
-        #[cfg(windows)]
-        queue!(stdout(), SetForegroundColor(Color::Grey),).unwrap();
-
+        set_region(ThemeRegion::Instructions);
         queue!(
             stdout(),
             Print(self.render_instructions()),
             MoveToNextLine(1)
         )
         .unwrap();
+        // Reset colors so none of this frame's theme bleeds into the
+        // terminal's own prompt or the next program's output.
+        queue!(stdout(), ResetColor).unwrap();
         // Print queued.
         stdout().flush().unwrap();
     }
 }
+
+impl Application {
+    /// Prints one frame as plain appended text: no screen clear, cursor
+    /// movement or ANSI color codes, just the frame's lines followed by a
+    /// newline so consecutive frames stay readable when redirected to a file.
+    fn render_plain(&self) {
+        println!("{}", TITLE);
+        println!("{}", self.render_image());
+        println!("{}", self.render_game_status());
+        println!("{}", self.render_secret());
+        println!("{}", self.render_instructions());
+        io::stdout().flush().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(a: &[&str]) -> impl Iterator<Item = String> {
+        a.iter().map(|s| s.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    #[test]
+    fn parse_args_rejects_zero_lives() {
+        match parse_args(args(&["--lives", "0"])) {
+            Operation::Error(_) => (),
+            _ => panic!("`--lives 0` must be rejected"),
+        }
+    }
+
+    #[test]
+    fn parse_args_rejects_missing_lives_value() {
+        match parse_args(args(&["--lives"])) {
+            Operation::Error(_) => (),
+            _ => panic!("`--lives` without a value must be rejected"),
+        }
+    }
+
+    #[test]
+    fn parse_args_rejects_missing_mode_value() {
+        match parse_args(args(&["--mode"])) {
+            Operation::Error(_) => (),
+            _ => panic!("`--mode` without a value must be rejected"),
+        }
+    }
+
+    #[test]
+    fn parse_args_rejects_missing_config_value() {
+        match parse_args(args(&["--config"])) {
+            Operation::Error(_) => (),
+            _ => panic!("`--config` without a value must be rejected"),
+        }
+    }
+
+    #[test]
+    fn parse_args_collects_repeated_config() {
+        match parse_args(args(&["--config", "a.txt", "--config", "b.txt"])) {
+            Operation::Play(opts) => assert_eq!(
+                opts.config_files,
+                vec![PathBuf::from("a.txt"), PathBuf::from("b.txt")]
+            ),
+            _ => panic!("repeated `--config` must be collected, not rejected"),
+        }
+    }
+
+    #[test]
+    fn toml_config_to_legacy_round_trip() {
+        let toml_src = r#"
+            secrets = ["_hang_man_"]
+            ascii_art = ["/o\\", "/|\\"]
+
+            [game]
+            lives = 7
+            mode = "traditional-rewarding"
+        "#;
+
+        let (legacy, lives) = toml_config_to_legacy(toml_src).unwrap();
+
+        assert_eq!(lives, Some(7));
+        assert_eq!(
+            legacy,
+            ":traditional-rewarding\n_hang_man_\n|/o\\\n|/|\\\n"
+        );
+    }
+
+    #[test]
+    fn toml_config_to_legacy_rejects_malformed_toml() {
+        let err = toml_config_to_legacy("this is not valid toml").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}